@@ -0,0 +1,16 @@
+//! # Interrupt Handlers module
+//! Defines the exception handlers the Kernel hooks into, wiring hardware interrupts to Kernel
+//! routines.
+
+use cortex_m_rt::exception;
+
+use crate::task_manager::{schedule, tick};
+
+/// SysTick fires every `SYSTICK_INTERRUPT_INTERVAL` clock cycles and drives the Kernel's notion of time.
+/// It advances the monotonic tick counter and releases any timer queue entries that are now due,
+/// then calls `schedule` so a task released by this tick can preempt the one currently running.
+#[exception]
+fn SysTick() {
+    tick();
+    schedule();
+}
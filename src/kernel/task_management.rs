@@ -6,6 +6,7 @@ use cortex_m::interrupt::free as execute_critical;
 use cortex_m::interrupt::Mutex;
 use core::cell::RefCell;
 
+use crate::config::MAX_TASKS;
 use crate::KernelError;
 use crate::priv_execute;
 use crate::system::task_manager::*;
@@ -47,6 +48,13 @@ where
     T: Sync,
 {
     priv_execute!({
+        for word in stack.iter_mut() {
+            *word = STACK_SENTINEL;
+        }
+        execute_critical(|cs_token| {
+            metrics.borrow(cs_token).borrow_mut().tasks[priority as usize].stack =
+                Some((stack.as_ptr(), stack.len()));
+        });
         execute_critical(|cs_token| unsafe {
             all_tasks.borrow(cs_token).borrow_mut().create_task(priority as usize, stack, handler_fn, param)
         })
@@ -78,6 +86,10 @@ pub fn preempt() {
                 cortex_m::peripheral::SCB::set_pendsv();
                 handler.curr_tid = os_next_task_id;
             }
+            let mut state = metrics.borrow(cs_token).borrow_mut();
+            state.context_switches = state.context_switches.wrapping_add(1);
+            state.tasks[next_tid as usize].dispatch_count =
+                state.tasks[next_tid as usize].dispatch_count.wrapping_add(1);
         }
     })
 }
@@ -111,5 +123,338 @@ pub fn task_exit() {
 
 /// The Kernel releases the tasks in the `task_mask`, these tasks transition from the waiting to the ready state.
 pub fn release(tasks_mask: BooleanVector) {
-    execute_critical(|cs_token| all_tasks.borrow(cs_token).borrow_mut().release(tasks_mask));
+    execute_critical(|cs_token| {
+        all_tasks.borrow(cs_token).borrow_mut().release(tasks_mask);
+        let mut state = metrics.borrow(cs_token).borrow_mut();
+        for tid in 0..MAX_TASKS {
+            if tasks_mask & (1 << tid as u32) != 0 {
+                state.tasks[tid].release_count = state.tasks[tid].release_count.wrapping_add(1);
+            }
+        }
+    });
+}
+
+/// Posts `payload` to the mailbox of every task in `tasks_mask` and releases them. A task reads
+/// the payload with `crate::messaging::read_payload` instead of its static `param`. Returns
+/// `KernelError::BufferOverflow` if any of them has an unread payload from a previous call.
+pub fn release_with<T: Copy>(tasks_mask: BooleanVector, payload: T) -> Result<(), KernelError> {
+    crate::messaging::post_payload_mask(tasks_mask, payload)?;
+    release(tasks_mask);
+    Ok(())
+}
+
+/// Terminates `tid` on behalf of another task or an interrupt handler, tearing down its locks,
+/// timer queue entries and mailbox and resetting its stack so it can be re-created. Returns
+/// `KernelError::DoesNotExist` if `tid` is not currently active.
+pub fn abort_task(tid: TaskId) -> Result<(), KernelError> {
+    priv_execute!({
+        let was_current = execute_critical(|cs_token| -> Result<bool, KernelError> {
+            let mut handler = all_tasks.borrow(cs_token).borrow_mut();
+            if handler.active_tasks & (1 << tid as u32) == 0 {
+                return Err(KernelError::DoesNotExist);
+            }
+            handler.active_tasks &= !(1 << tid as u32);
+            let was_current = handler.curr_tid as TaskId == tid;
+            handler.reset_stack(tid as usize);
+            Ok(was_current)
+        })?;
+
+        execute_critical(|cs_token| timer_queue.borrow(cs_token).borrow_mut().remove_task(tid));
+        crate::messaging::clear_mailbox(tid);
+        crate::resources::release_locks_held_by(tid);
+
+        if was_current {
+            schedule();
+        }
+        Ok(())
+    })
+}
+
+/// A single entry in the [`TimerQueue`], released once `deadline` is reached. `period` is set for
+/// entries created through [`schedule_periodic`] so the entry can be re-armed after it fires.
+#[derive(Clone, Copy)]
+struct TimerEntry {
+    deadline: u32,
+    mask: BooleanVector,
+    period: Option<u32>,
+}
+
+/// Fixed-capacity, deadline-sorted queue of pending [`schedule_after`]/[`schedule_periodic`]
+/// releases. Capacity is bounded by `MAX_TASKS` since at most one pending entry is expected per task.
+struct TimerQueue {
+    entries: [Option<TimerEntry>; MAX_TASKS],
+    len: usize,
+}
+
+impl TimerQueue {
+    const fn new() -> Self {
+        Self {
+            entries: [None; MAX_TASKS],
+            len: 0,
+        }
+    }
+
+    /// Inserts `entry` keeping `entries[..len]` sorted by ascending deadline.
+    fn insert(&mut self, entry: TimerEntry) -> Result<(), KernelError> {
+        if self.len == MAX_TASKS {
+            return Err(KernelError::LimitExceeded);
+        }
+        let mut idx = self.len;
+        while idx > 0 {
+            let prev = self.entries[idx - 1].unwrap();
+            if prev.deadline.wrapping_sub(entry.deadline) as i32 <= 0 {
+                break;
+            }
+            self.entries[idx] = self.entries[idx - 1];
+            idx -= 1;
+        }
+        self.entries[idx] = Some(entry);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Clears `tid`'s bit from every pending entry's mask, dropping entries whose mask becomes
+    /// empty as a result. Used to tear down a task's pending deferred/periodic releases on abort.
+    fn remove_task(&mut self, tid: TaskId) {
+        let bit = 1 << tid as u32;
+        let mut write = 0;
+        for read in 0..self.len {
+            let mut entry = self.entries[read].unwrap();
+            entry.mask &= !bit;
+            if entry.mask != 0 {
+                self.entries[write] = Some(entry);
+                write += 1;
+            }
+        }
+        for idx in write..self.len {
+            self.entries[idx] = None;
+        }
+        self.len = write;
+    }
+
+    /// Removes and returns the earliest entry if it is due at `now`, comparing deadlines with a
+    /// wrapping subtraction so the tick counter can safely roll over.
+    fn pop_due(&mut self, now: u32) -> Option<TimerEntry> {
+        let head = self.entries[0]?;
+        if now.wrapping_sub(head.deadline) as i32 >= 0 {
+            for idx in 1..self.len {
+                self.entries[idx - 1] = self.entries[idx];
+            }
+            self.len -= 1;
+            self.entries[self.len] = None;
+            Some(head)
+        } else {
+            None
+        }
+    }
+}
+
+/// Monotonic tick counter incremented once per SysTick interrupt.
+static tick_count: Mutex<RefCell<u32>> = Mutex::new(RefCell::new(0));
+
+/// Global queue of pending deferred/periodic task releases, drained on every tick.
+static timer_queue: Mutex<RefCell<TimerQueue>> = Mutex::new(RefCell::new(TimerQueue::new()));
+
+/// Releases `tasks` once `ticks` SysTick interrupts have elapsed from now.
+pub fn schedule_after(ticks: u32, tasks: BooleanVector) -> Result<(), KernelError> {
+    execute_critical(|cs_token| {
+        let now = *tick_count.borrow(cs_token).borrow();
+        let entry = TimerEntry {
+            deadline: now.wrapping_add(ticks),
+            mask: tasks,
+            period: None,
+        };
+        timer_queue.borrow(cs_token).borrow_mut().insert(entry)
+    })
+}
+
+/// Releases `tasks` every `period` SysTick interrupts, starting `period` ticks from now. `period`
+/// must be non-zero: a zero period would re-arm to the same deadline it just fired at, so `tick`
+/// would keep popping and re-releasing it forever.
+pub fn schedule_periodic(period: u32, tasks: BooleanVector) -> Result<(), KernelError> {
+    if period == 0 {
+        return Err(KernelError::LimitExceeded);
+    }
+    execute_critical(|cs_token| {
+        let now = *tick_count.borrow(cs_token).borrow();
+        let entry = TimerEntry {
+            deadline: now.wrapping_add(period),
+            mask: tasks,
+            period: Some(period),
+        };
+        timer_queue.borrow(cs_token).borrow_mut().insert(entry)
+    })
+}
+
+/// Advances the monotonic tick counter and releases every timer queue entry whose deadline has
+/// elapsed, re-arming periodic entries for their next activation. Called from the SysTick handler.
+pub fn tick() {
+    let now = execute_critical(|cs_token| {
+        let mut count = tick_count.borrow(cs_token).borrow_mut();
+        *count = count.wrapping_add(1);
+        *count
+    });
+    execute_critical(|cs_token| {
+        let mut state = metrics.borrow(cs_token).borrow_mut();
+        state.systick_count = state.systick_count.wrapping_add(1);
+        if all_tasks.borrow(cs_token).borrow().curr_tid == 0 {
+            state.idle_ticks = state.idle_ticks.wrapping_add(1);
+        }
+    });
+    loop {
+        let due = execute_critical(|cs_token| timer_queue.borrow(cs_token).borrow_mut().pop_due(now));
+        match due {
+            Some(entry) => {
+                if entry.period.is_some() {
+                    record_deadline_misses(entry.mask);
+                }
+                release(entry.mask);
+                if let Some(period) = entry.period {
+                    let rearmed = TimerEntry {
+                        deadline: entry.deadline.wrapping_add(period),
+                        mask: entry.mask,
+                        period: Some(period),
+                    };
+                    execute_critical(|cs_token| {
+                        let _ = timer_queue.borrow(cs_token).borrow_mut().insert(rearmed);
+                    });
+                }
+            }
+            None => break,
+        }
+    }
+}
+
+/// Records a deadline-missed event for every task in `mask` whose `active_tasks` bit is still set,
+/// meaning a periodic release landed before the previous activation cleared it (via `task_exit` or
+/// `abort_task`).
+fn record_deadline_misses(mask: BooleanVector) {
+    execute_critical(|cs_token| {
+        let still_active = all_tasks.borrow(cs_token).borrow().active_tasks & mask;
+        if still_active == 0 {
+            return;
+        }
+        let mut state = metrics.borrow(cs_token).borrow_mut();
+        for tid in 0..MAX_TASKS {
+            if still_active & (1 << tid as u32) != 0 {
+                state.tasks[tid].deadline_misses = state.tasks[tid].deadline_misses.wrapping_add(1);
+            }
+        }
+    });
+}
+
+/// Sentinel word used to "paint" a task's stack at `create_task` time, so the unused portion can
+/// later be recognised when computing its peak stack usage.
+const STACK_SENTINEL: u32 = 0xDEAD_C0DE;
+
+/// Per-task instrumentation tracked by the metrics subsystem. `stack` records the task's stack
+/// slice (set once, at `create_task` time) so `peak_stack_words` can be derived on demand rather
+/// than tracked incrementally.
+#[derive(Clone, Copy)]
+struct TaskMetrics {
+    dispatch_count: u32,
+    release_count: u32,
+    deadline_misses: u32,
+    stack: Option<(*const u32, usize)>,
+}
+
+impl TaskMetrics {
+    const fn new() -> Self {
+        Self {
+            dispatch_count: 0,
+            release_count: 0,
+            deadline_misses: 0,
+            stack: None,
+        }
+    }
+}
+
+/// Scheduler-wide and per-task counters backing [`get_metrics`]/[`reset_metrics`].
+struct Metrics {
+    tasks: [TaskMetrics; MAX_TASKS],
+    context_switches: u32,
+    systick_count: u32,
+    idle_ticks: u32,
+}
+
+impl Metrics {
+    const fn new() -> Self {
+        Self {
+            tasks: [TaskMetrics::new(); MAX_TASKS],
+            context_switches: 0,
+            systick_count: 0,
+            idle_ticks: 0,
+        }
+    }
+}
+
+static metrics: Mutex<RefCell<Metrics>> = Mutex::new(RefCell::new(Metrics::new()));
+
+/// `Copy` snapshot of one task's instrumentation, as returned inside [`SchedulerMetrics`].
+#[derive(Clone, Copy, Default)]
+pub struct TaskMetricsSnapshot {
+    pub dispatch_count: u32,
+    pub release_count: u32,
+    pub deadline_misses: u32,
+    pub peak_stack_words: usize,
+}
+
+/// Snapshot of the scheduler's instrumentation, returned by [`get_metrics`]. Counters cover the
+/// time since start-up or the last [`reset_metrics`] call; `per_task` is indexed by `TaskId`.
+#[derive(Clone, Copy)]
+pub struct SchedulerMetrics {
+    pub per_task: [TaskMetricsSnapshot; MAX_TASKS],
+    pub context_switches: u32,
+    pub systick_count: u32,
+    pub idle_ticks: u32,
+}
+
+/// Scans a sentinel-painted stack for its high-water mark: the point closest to the stack's limit
+/// that has been overwritten since `create_task` painted it.
+fn stack_high_water_mark(stack_base: *const u32, len: usize) -> usize {
+    let slice = unsafe { core::slice::from_raw_parts(stack_base, len) };
+    let untouched = slice.iter().take_while(|&&word| word == STACK_SENTINEL).count();
+    len - untouched
+}
+
+/// Returns a `Copy` snapshot of the scheduler's instrumentation, taken under `execute_critical`.
+pub fn get_metrics() -> SchedulerMetrics {
+    execute_critical(|cs_token| {
+        let state = metrics.borrow(cs_token).borrow();
+        let mut per_task = [TaskMetricsSnapshot::default(); MAX_TASKS];
+        for (tid, task) in state.tasks.iter().enumerate() {
+            per_task[tid] = TaskMetricsSnapshot {
+                dispatch_count: task.dispatch_count,
+                release_count: task.release_count,
+                deadline_misses: task.deadline_misses,
+                peak_stack_words: match task.stack {
+                    Some((base, len)) => stack_high_water_mark(base, len),
+                    None => 0,
+                },
+            };
+        }
+        SchedulerMetrics {
+            per_task,
+            context_switches: state.context_switches,
+            systick_count: state.systick_count,
+            idle_ticks: state.idle_ticks,
+        }
+    })
+}
+
+/// Resets every counter tracked by [`get_metrics`] back to zero. Stack high-water marks are
+/// unaffected since they are derived on demand from the sentinel pattern painted at `create_task`
+/// time, not from a counter.
+pub fn reset_metrics() {
+    execute_critical(|cs_token| {
+        let mut state = metrics.borrow(cs_token).borrow_mut();
+        for task in state.tasks.iter_mut() {
+            task.dispatch_count = 0;
+            task.release_count = 0;
+            task.deadline_misses = 0;
+        }
+        state.context_switches = 0;
+        state.systick_count = 0;
+        state.idle_ticks = 0;
+    });
 }
\ No newline at end of file
@@ -17,12 +17,20 @@ use crate::errors::KernelError;
 use core::fmt;
 
 pub mod tasks {
+    pub use crate::task_manager::abort_task;
     pub use crate::task_manager::create_task;
+    pub use crate::task_manager::get_metrics;
     pub use crate::task_manager::init;
     pub use crate::task_manager::release_tasks;
+    pub use crate::task_manager::release_with;
+    pub use crate::task_manager::reset_metrics;
+    pub use crate::task_manager::schedule_after;
+    pub use crate::task_manager::schedule_periodic;
     pub use crate::task_manager::start_kernel;
     pub use crate::task_manager::task_exit;
+    pub use crate::task_manager::SchedulerMetrics;
     pub use crate::task_manager::TaskId;
+    pub use crate::messaging::read_payload;
 }
 
 mod config {
@@ -0,0 +1,102 @@
+//! # Messaging module
+//! Provides the fixed-capacity, per-task mailbox used to hand a task fresh data at release time
+//! (`task_manager::release_with`). Each mailbox is a raw byte buffer of `MAX_BUFFER_SIZE` bytes.
+
+use core::cell::RefCell;
+use core::mem::size_of;
+use cortex_m::interrupt::free as execute_critical;
+use cortex_m::interrupt::Mutex;
+
+use crate::config::{MAX_BUFFER_SIZE, MCB_COUNT};
+use crate::system::types::{BooleanVector, TaskId};
+use crate::KernelError;
+
+/// A single-slot message control block: a fixed byte buffer big enough to hold any payload posted
+/// through `release_with`, plus a flag marking whether it holds an unread value.
+#[derive(Clone, Copy)]
+struct Mcb {
+    buffer: [u8; MAX_BUFFER_SIZE],
+    occupied: bool,
+}
+
+impl Mcb {
+    const fn new() -> Self {
+        Self {
+            buffer: [0; MAX_BUFFER_SIZE],
+            occupied: false,
+        }
+    }
+}
+
+/// One mailbox slot per task, indexed by `TaskId`; the number of slots is `MCB_COUNT`.
+static mailboxes: Mutex<RefCell<[Mcb; MCB_COUNT]>> =
+    Mutex::new(RefCell::new([Mcb::new(); MCB_COUNT]));
+
+/// Posts `payload` to `tid`'s mailbox. Returns `KernelError::BufferOverflow` if `T` is too large or the previous payload hasn't been read yet.
+pub fn post_payload<T: Copy>(tid: TaskId, payload: T) -> Result<(), KernelError> {
+    if size_of::<T>() > MAX_BUFFER_SIZE {
+        return Err(KernelError::BufferOverflow);
+    }
+    execute_critical(|cs_token| {
+        let mut boxes = mailboxes.borrow(cs_token).borrow_mut();
+        let mcb = &mut boxes[tid as usize];
+        if mcb.occupied {
+            return Err(KernelError::BufferOverflow);
+        }
+        let bytes =
+            unsafe { core::slice::from_raw_parts(&payload as *const T as *const u8, size_of::<T>()) };
+        mcb.buffer[..bytes.len()].copy_from_slice(bytes);
+        mcb.occupied = true;
+        Ok(())
+    })
+}
+
+/// Reads and clears `tid`'s mailbox. Returns `Ok(None)` if no payload is pending.
+pub fn read_payload<T: Copy>(tid: TaskId) -> Result<Option<T>, KernelError> {
+    if size_of::<T>() > MAX_BUFFER_SIZE {
+        return Err(KernelError::BufferOverflow);
+    }
+    execute_critical(|cs_token| {
+        let mut boxes = mailboxes.borrow(cs_token).borrow_mut();
+        let mcb = &mut boxes[tid as usize];
+        if !mcb.occupied {
+            return Ok(None);
+        }
+        mcb.occupied = false;
+        // buffer has alignment 1, so T (which may need a stricter alignment) can't be read with a
+        // plain dereference.
+        Ok(Some(unsafe { core::ptr::read_unaligned(mcb.buffer.as_ptr() as *const T) }))
+    })
+}
+
+/// Posts `payload` to every task in `mask`'s mailbox in one critical section; either all of them receive it, or none do.
+pub(crate) fn post_payload_mask<T: Copy>(mask: BooleanVector, payload: T) -> Result<(), KernelError> {
+    if size_of::<T>() > MAX_BUFFER_SIZE {
+        return Err(KernelError::BufferOverflow);
+    }
+    execute_critical(|cs_token| {
+        let mut boxes = mailboxes.borrow(cs_token).borrow_mut();
+        for tid in 0..MCB_COUNT {
+            if mask & (1 << tid as u32) != 0 && boxes[tid].occupied {
+                return Err(KernelError::BufferOverflow);
+            }
+        }
+        let bytes =
+            unsafe { core::slice::from_raw_parts(&payload as *const T as *const u8, size_of::<T>()) };
+        for tid in 0..MCB_COUNT {
+            if mask & (1 << tid as u32) != 0 {
+                boxes[tid].buffer[..bytes.len()].copy_from_slice(bytes);
+                boxes[tid].occupied = true;
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Clears `tid`'s mailbox without reading it, dropping any payload it was still owed. Used by
+/// `task_manager::abort_task` when tearing down a killed task.
+pub(crate) fn clear_mailbox(tid: TaskId) {
+    execute_critical(|cs_token| {
+        mailboxes.borrow(cs_token).borrow_mut()[tid as usize].occupied = false;
+    });
+}
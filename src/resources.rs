@@ -0,0 +1,112 @@
+//! # Resources module
+//! Implements the Immediate Ceiling Priority Protocol (ICPP) for safe, deadlock-free sharing of
+//! data across tasks running at different priorities.
+
+use core::cell::{RefCell, UnsafeCell};
+use cortex_m::interrupt::free as execute_critical;
+use cortex_m::interrupt::Mutex;
+
+use crate::config::MAX_RESOURCES;
+use crate::system::types::{BooleanVector, TaskId};
+use crate::task_manager::{all_tasks, get_curr_tid, unblock_tasks};
+use crate::KernelError;
+
+/// Entry on `blocked_stack`: the task that took the lock and the mask it blocked to do so.
+#[derive(Clone, Copy)]
+struct BlockedEntry {
+    owner: TaskId,
+    mask: BooleanVector,
+}
+
+/// Stack of the masks blocked by each currently held lock, tagged with the owning task.
+static blocked_stack: Mutex<RefCell<([BlockedEntry; MAX_RESOURCES], usize)>> = Mutex::new(RefCell::new((
+    [BlockedEntry {
+        owner: 0,
+        mask: 0,
+    }; MAX_RESOURCES],
+    0,
+)));
+
+/// A `Resource` wraps shared data behind the Immediate Ceiling Priority Protocol: locking it raises
+/// the running task to the resource's static ceiling for the duration of the critical section.
+pub struct Resource<T> {
+    data: UnsafeCell<T>,
+    ceiling_mask: BooleanVector,
+}
+
+unsafe impl<T> Sync for Resource<T> {}
+
+impl<T> Resource<T> {
+    /// Creates a new resource. `ceiling_mask` is the static ceiling of the resource, expressed as
+    /// a `BooleanVector` of every task with priority less than or equal to the highest-priority
+    /// task permitted to access it.
+    pub const fn new(data: T, ceiling_mask: BooleanVector) -> Self {
+        Self {
+            data: UnsafeCell::new(data),
+            ceiling_mask,
+        }
+    }
+
+    /// Locks the resource for the duration of `locking_fn`, blocking every task that could contend
+    /// for it. Returns `KernelError::LimitExceeded` instead of locking if `blocked_stack` is full.
+    pub fn lock<F, R>(&self, locking_fn: F) -> Result<R, KernelError>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let curr_tid = get_curr_tid();
+        // The bound check, stack push and scheduler block all happen in one critical section so
+        // no interrupt can slip in and schedule a task the ceiling is meant to keep blocked.
+        execute_critical(|cs_token| -> Result<(), KernelError> {
+            let mut stack = blocked_stack.borrow(cs_token).borrow_mut();
+            let (entries, len) = &mut *stack;
+            if *len == MAX_RESOURCES {
+                return Err(KernelError::LimitExceeded);
+            }
+            let blocked = self.ceiling_mask & !(1 << curr_tid as u32);
+            entries[*len] = BlockedEntry {
+                owner: curr_tid,
+                mask: blocked,
+            };
+            *len += 1;
+            all_tasks.borrow(cs_token).borrow_mut().block_tasks(blocked);
+            Ok(())
+        })?;
+
+        let result = locking_fn(unsafe { &mut *self.data.get() });
+
+        execute_critical(|cs_token| {
+            let mut stack = blocked_stack.borrow(cs_token).borrow_mut();
+            let (entries, len) = &mut *stack;
+            *len -= 1;
+            // Keep blocked any task that an outer lock still held needs blocked.
+            let still_blocked = entries[..*len].iter().fold(0, |acc, entry| acc | entry.mask);
+            let restored = entries[*len].mask & !still_blocked;
+            all_tasks.borrow(cs_token).borrow_mut().unblock_tasks(restored);
+        });
+
+        Ok(result)
+    }
+}
+
+/// Releases every priority-ceiling lock currently held by `tid`, restoring the tasks each one blocked unless a lock that is still held blocks them too. Used by `task_manager::abort_task`.
+pub(crate) fn release_locks_held_by(tid: TaskId) {
+    let restored = execute_critical(|cs_token| {
+        let mut stack = blocked_stack.borrow(cs_token).borrow_mut();
+        let (entries, len) = &mut *stack;
+        let mut restored: BooleanVector = 0;
+        let mut write = 0;
+        for read in 0..*len {
+            let entry = entries[read];
+            if entry.owner == tid {
+                restored |= entry.mask;
+            } else {
+                entries[write] = entry;
+                write += 1;
+            }
+        }
+        *len = write;
+        let still_blocked = entries[..write].iter().fold(0, |acc, entry| acc | entry.mask);
+        restored & !still_blocked
+    });
+    unblock_tasks(restored);
+}